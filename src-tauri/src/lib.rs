@@ -1,17 +1,292 @@
+mod window_state;
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex,
+};
+
 use tauri::{
-    menu::{Menu, MenuItem},
+    menu::{AboutMetadataBuilder, CheckMenuItem, Menu, MenuItem, PredefinedMenuItem},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Manager, WindowEvent, PhysicalPosition, Position, Size,
+    AppHandle, Emitter, Manager, WindowEvent, PhysicalPosition, PhysicalSize, Position, Rect, Size,
+    WebviewWindow,
 };
+use tauri_plugin_autostart::ManagerExt;
+use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut, ShortcutState};
+use window_state::WindowStateManager;
+
+/// Remembers the tray icon's last known rect so `toggle_panel` can position
+/// the panel relative to the tray even when triggered from the global
+/// shortcut, which has no rect of its own.
+#[derive(Default)]
+struct LastTrayRect(Mutex<Option<Rect>>);
+
+fn remember_tray_rect(app: &AppHandle, rect: Rect) {
+    *app.state::<LastTrayRect>().0.lock().unwrap() = Some(rect);
+}
+
+/// Tracks the current mood/conversion status shown in the tray tooltip on
+/// hover. Defaults to an idle message until `set_tray_status` updates it.
+struct TrayStatus(std::sync::Mutex<String>);
+
+impl Default for TrayStatus {
+    fn default() -> Self {
+        Self(std::sync::Mutex::new("Moodverter — ready".to_string()))
+    }
+}
+
+impl TrayStatus {
+    fn current(&self) -> String {
+        self.0.lock().unwrap().clone()
+    }
+
+    fn set(&self, status: String) {
+        *self.0.lock().unwrap() = status;
+    }
+}
+
+/// Command for the frontend to report the current mood/conversion status,
+/// which the tray tooltip shows on the next hover.
+#[tauri::command]
+fn set_tray_status(app: AppHandle, status: String) {
+    app.state::<TrayStatus>().set(status);
+}
+
+/// When pinned, the panel stays visible on focus loss so the user can work
+/// with file dialogs, color pickers, or other windows without it vanishing.
+#[derive(Default)]
+struct PinState(AtomicBool);
+
+impl PinState {
+    fn is_pinned(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn toggle(&self) -> bool {
+        let pinned = !self.is_pinned();
+        self.0.store(pinned, Ordering::Relaxed);
+        pinned
+    }
+}
+
+/// Handles to the tray's checkbox items, kept in managed state so their
+/// checked glyph can be synced after the underlying setting is toggled.
+/// `app.menu()`/`window.menu()` only resolve the app/window menu bar set via
+/// `set_menu` — the tray's context menu is a separate object reachable only
+/// through the `Menu` handed to `TrayIconBuilder::menu`, so the items
+/// themselves need to be stashed here instead.
+struct TrayCheckItems {
+    pin: CheckMenuItem<tauri::Wry>,
+    launch_at_login: CheckMenuItem<tauri::Wry>,
+}
+
+/// Checks whether a physical point falls inside the work area of any
+/// currently connected monitor, so a saved position from a previous monitor
+/// layout isn't trusted blindly.
+fn position_on_any_monitor(window: &WebviewWindow, position: PhysicalPosition<i32>) -> bool {
+    let Ok(monitors) = window.available_monitors() else {
+        return false;
+    };
+    monitors.iter().any(|monitor| {
+        let work_area = monitor.work_area();
+        position.x >= work_area.position.x
+            && position.x < work_area.position.x + work_area.size.width as i32
+            && position.y >= work_area.position.y
+            && position.y < work_area.position.y + work_area.size.height as i32
+    })
+}
+
+/// Computes and applies a panel position near the tray icon, clamped to the
+/// work area of the monitor the panel window is currently on
+/// (`current_monitor`); the monitor under the tray rect is not looked up
+/// separately. Leaves the window where it is if it has no current monitor.
+/// No-ops once the user has manually dragged the panel, so it stops snapping
+/// back under the tray icon.
+fn position_panel_near_tray(window: &WebviewWindow, tray_rect_pos: Position, tray_rect_size: Size) {
+    let state_manager = window.app_handle().state::<WindowStateManager>();
+    if state_manager.is_user_positioned() {
+        return;
+    }
+
+    let monitor = match window.current_monitor() {
+        Ok(Some(monitor)) => monitor,
+        _ => return,
+    };
+
+    let scale_factor = monitor.scale_factor();
+    let work_area = monitor.work_area();
+
+    // Normalize everything to physical pixels so logical/physical Position
+    // and Size variants produce identical results.
+    let (tray_x, tray_y) = match tray_rect_pos {
+        Position::Physical(pos) => (pos.x as f64, pos.y as f64),
+        Position::Logical(pos) => (pos.x * scale_factor, pos.y * scale_factor),
+    };
+    let tray_height = match tray_rect_size {
+        Size::Physical(size) => size.height as f64,
+        Size::Logical(size) => size.height * scale_factor,
+    };
+
+    let window_size = window
+        .outer_size()
+        .unwrap_or(PhysicalSize::new(400, 500));
+    let window_width = window_size.width as f64;
+    let window_height = window_size.height as f64;
+
+    let work_x = work_area.position.x as f64;
+    let work_y = work_area.position.y as f64;
+    let work_width = work_area.size.width as f64;
+    let work_height = work_area.size.height as f64;
+
+    // Prefer below the tray icon; flip above it if that would overflow the
+    // bottom of the monitor's work area.
+    let below_y = tray_y + tray_height + 5.0;
+    let y = if below_y + window_height > work_y + work_height {
+        tray_y - window_height - 5.0
+    } else {
+        below_y
+    };
+
+    // If the panel is wider than the monitor's work area, the upper bound
+    // would fall below `work_x` and this would overhang the left edge
+    // instead of clamping to it — floor it at `work_x` so it degrades to
+    // left-aligned rather than negative-overhanging.
+    let x = (tray_x - window_width / 2.0)
+        .max(work_x)
+        .min((work_x + work_width - window_width).max(work_x));
+
+    state_manager.begin_programmatic_move();
+    let _ = window.set_position(PhysicalPosition::new(x as i32, y as i32));
+}
+
+/// Shows/hides the panel, positioning it near the tray icon. Shared by the
+/// tray left-click handler and the global shortcut so both stay consistent.
+fn toggle_panel(app: &AppHandle) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    if window.is_visible().unwrap_or(false) {
+        let _ = window.hide();
+        return;
+    }
+    if let Some(rect) = *app.state::<LastTrayRect>().0.lock().unwrap() {
+        position_panel_near_tray(&window, rect.position, rect.size);
+    }
+    let _ = window.show();
+    let _ = window.set_focus();
+}
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_autostart::init(
+            tauri_plugin_autostart::MacosLauncher::LaunchAgent,
+            None,
+        ))
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(|app, _shortcut, event| {
+                    if event.state() == ShortcutState::Pressed {
+                        toggle_panel(app);
+                    }
+                })
+                .build(),
+        )
+        .invoke_handler(tauri::generate_handler![set_tray_status])
         .setup(|app| {
+            // Behave like a true menubar utility: no Dock icon, and never
+            // steal the foreground when the tray icon is clicked.
+            #[cfg(target_os = "macos")]
+            app.set_activation_policy(tauri::ActivationPolicy::Accessory);
+
+            // Manage app state up front (the tray's check items are managed
+            // once they exist, further below), before anything below touches
+            // window geometry — `on_window_event`'s Moved/Resized arms fetch
+            // `WindowStateManager` unconditionally, so it must already be
+            // managed before `set_size`/`set_position` can trigger them.
+            let saved_state = window_state::load(app.handle());
+            let restored_position = saved_state.user_positioned.then(|| {
+                PhysicalPosition::new(saved_state.x, saved_state.y)
+            });
+            let restored_size = PhysicalSize::new(saved_state.width, saved_state.height);
+            app.manage(WindowStateManager::new(saved_state, app.handle()));
+            app.manage(TrayStatus::default());
+            app.manage(LastTrayRect::default());
+            app.manage(PinState::default());
+
+            // Restore the persisted window geometry loaded above before the
+            // panel is first shown. A saved position is only trusted if it
+            // still lands on a connected monitor's work area — otherwise
+            // (e.g. a laptop undocked, or a monitor layout change) the panel
+            // would silently restore off-screen on every future launch, so
+            // drop the "user dragged it" latch and fall back to auto-snap.
+            let state_manager = app.state::<WindowStateManager>();
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.set_size(restored_size);
+                match restored_position {
+                    Some(position) if position_on_any_monitor(&window, position) => {
+                        state_manager.begin_programmatic_move();
+                        let _ = window.set_position(position);
+                    }
+                    Some(_) => state_manager.clear_user_positioned(),
+                    None => {}
+                }
+            }
+
+            // Register the global hotkey that toggles the panel from
+            // anywhere, mirroring the tray left-click behavior.
+            #[cfg(target_os = "macos")]
+            let toggle_modifiers = Modifiers::SUPER | Modifiers::SHIFT;
+            #[cfg(not(target_os = "macos"))]
+            let toggle_modifiers = Modifiers::CONTROL | Modifiers::SHIFT;
+            // Best-effort: a common combo may already be owned by another
+            // app, or the OS may deny the registration (e.g. a declined
+            // Wayland portal prompt). Either way, the hotkey is a nice-to-have
+            // and must not take the whole app down with it.
+            if let Err(err) = app
+                .global_shortcut()
+                .register(Shortcut::new(Some(toggle_modifiers), Code::KeyM))
+            {
+                eprintln!("failed to register global toggle shortcut: {err}");
+            }
+
             // Create tray menu items
+            let open_item = MenuItem::with_id(app, "open", "Open Moodverter", true, None::<&str>)?;
+            let convert_clipboard_item =
+                MenuItem::with_id(app, "convert_clipboard", "Convert clipboard", true, None::<&str>)?;
+            let pin_item = CheckMenuItem::with_id(app, "pin", "Pin panel", true, false, None::<&str>)?;
+            let launch_at_login_item = CheckMenuItem::with_id(
+                app,
+                "launch_at_login",
+                "Launch at login",
+                true,
+                app.autolaunch().is_enabled().unwrap_or(false),
+                None::<&str>,
+            )?;
+            let about_metadata = AboutMetadataBuilder::new()
+                .name(Some("Moodverter"))
+                .version(Some(env!("CARGO_PKG_VERSION")))
+                .build();
+            let about_item = PredefinedMenuItem::about(app, Some("About Moodverter"), Some(about_metadata))?;
             let quit_item = MenuItem::with_id(app, "quit", "Quit Moodverter", true, None::<&str>)?;
-            let menu = Menu::with_items(app, &[&quit_item])?;
+            app.manage(TrayCheckItems {
+                pin: pin_item.clone(),
+                launch_at_login: launch_at_login_item.clone(),
+            });
+            let menu = Menu::with_items(
+                app,
+                &[
+                    &open_item,
+                    &convert_clipboard_item,
+                    &PredefinedMenuItem::separator(app)?,
+                    &pin_item,
+                    &launch_at_login_item,
+                    &PredefinedMenuItem::separator(app)?,
+                    &about_item,
+                    &quit_item,
+                ],
+            )?;
 
             // Build the tray icon
             let _tray = TrayIconBuilder::new()
@@ -19,46 +294,66 @@ pub fn run() {
                 .menu(&menu)
                 .show_menu_on_left_click(false)
                 .tooltip("Moodverter")
-                .on_menu_event(|app, event| {
-                    if event.id.as_ref() == "quit" {
-                        app.exit(0);
+                .on_menu_event(|app, event| match event.id.as_ref() {
+                    "open" => {
+                        if let Some(window) = app.get_webview_window("main") {
+                            let _ = window.show();
+                            let _ = window.set_focus();
+                        }
                     }
+                    "convert_clipboard" => {
+                        if let Some(window) = app.get_webview_window("main") {
+                            let _ = window.show();
+                            let _ = window.set_focus();
+                            let _ = window.emit("convert-clipboard", ());
+                        }
+                    }
+                    "pin" => {
+                        let pinned = app.state::<PinState>().toggle();
+                        let _ = app.state::<TrayCheckItems>().pin.set_checked(pinned);
+                    }
+                    "launch_at_login" => {
+                        let autostart = app.autolaunch();
+                        let enabled = if autostart.is_enabled().unwrap_or(false) {
+                            let _ = autostart.disable();
+                            false
+                        } else {
+                            let _ = autostart.enable();
+                            true
+                        };
+                        let _ = app
+                            .state::<TrayCheckItems>()
+                            .launch_at_login
+                            .set_checked(enabled);
+                    }
+                    "quit" => app.exit(0),
+                    _ => {}
                 })
-                .on_tray_icon_event(|tray, event| {
+                .on_tray_icon_event(|tray, event| match event {
                     // Toggle window on left click, position below tray icon
-                    if let TrayIconEvent::Click {
+                    TrayIconEvent::Click {
                         button: MouseButton::Left,
                         button_state: MouseButtonState::Up,
                         rect,
                         ..
-                    } = event
-                    {
-                        let app = tray.app_handle();
-                        if let Some(window) = app.get_webview_window("main") {
-                            if window.is_visible().unwrap_or(false) {
-                                let _ = window.hide();
-                            } else {
-                                // Extract position values
-                                let (tray_x, tray_y) = match rect.position {
-                                    Position::Physical(pos) => (pos.x as f64, pos.y as f64),
-                                    Position::Logical(pos) => (pos.x, pos.y),
-                                };
-                                let tray_height = match rect.size {
-                                    Size::Physical(size) => size.height as f64,
-                                    Size::Logical(size) => size.height,
-                                };
-
-                                // Position window below tray icon (centered)
-                                let window_width: f64 = 400.0;
-                                let x = tray_x - (window_width / 2.0);
-                                let y = tray_y + tray_height + 5.0;
-
-                                let _ = window.set_position(PhysicalPosition::new(x as i32, y as i32));
-                                let _ = window.show();
-                                let _ = window.set_focus();
-                            }
-                        }
+                    } => {
+                        remember_tray_rect(tray.app_handle(), rect);
+                        toggle_panel(tray.app_handle());
+                    }
+                    // Show live status in the tooltip while the cursor is
+                    // hovering the tray icon, and restore the default on exit.
+                    TrayIconEvent::Enter { rect, .. } => {
+                        remember_tray_rect(tray.app_handle(), rect);
+                        let status = tray.app_handle().state::<TrayStatus>();
+                        let _ = tray.set_tooltip(Some(status.current()));
+                    }
+                    TrayIconEvent::Move { rect, .. } => {
+                        remember_tray_rect(tray.app_handle(), rect);
+                    }
+                    TrayIconEvent::Leave { .. } => {
+                        let _ = tray.set_tooltip(Some("Moodverter"));
                     }
+                    _ => {}
                 })
                 .build(app)?;
 
@@ -66,15 +361,36 @@ pub fn run() {
         })
         .on_window_event(|window, event| {
             match event {
-                // Hide window when it loses focus (click outside)
+                // Hide window when it loses focus (click outside), unless
+                // the user has pinned the panel open.
                 WindowEvent::Focused(false) => {
-                    let _ = window.hide();
+                    if !window.app_handle().state::<PinState>().is_pinned() {
+                        let _ = window.hide();
+                    }
                 }
                 // Prevent close, just hide
                 WindowEvent::CloseRequested { api, .. } => {
                     let _ = window.hide();
                     api.prevent_close();
                 }
+                // Debounce-persist geometry, and note once the user has
+                // dragged the panel so it stops auto-snapping to the tray.
+                WindowEvent::Moved(position) => {
+                    let state_manager = window.app_handle().state::<WindowStateManager>();
+                    if state_manager.take_suppressed_move() {
+                        return;
+                    }
+                    state_manager.mark_user_positioned();
+                    if let Ok(size) = window.outer_size() {
+                        state_manager.update_geometry(size, *position);
+                    }
+                }
+                WindowEvent::Resized(size) => {
+                    let state_manager = window.app_handle().state::<WindowStateManager>();
+                    if let Ok(position) = window.outer_position() {
+                        state_manager.update_geometry(*size, position);
+                    }
+                }
                 _ => {}
             }
         })
@@ -0,0 +1,144 @@
+use std::{
+    fs,
+    sync::{mpsc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, PhysicalPosition, PhysicalSize};
+
+const STATE_FILE_NAME: &str = "window-state.json";
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Persisted panel geometry, plus whether the user has manually repositioned
+/// the panel. Once `user_positioned` is set the panel stops snapping back
+/// under the tray icon on toggle and is instead restored to this spot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowState {
+    pub width: u32,
+    pub height: u32,
+    pub x: i32,
+    pub y: i32,
+    pub user_positioned: bool,
+}
+
+impl Default for WindowState {
+    fn default() -> Self {
+        Self {
+            width: 400,
+            height: 500,
+            x: 0,
+            y: 0,
+            user_positioned: false,
+        }
+    }
+}
+
+/// Shared, debounced window-state store. Managed as Tauri app state so both
+/// the `setup` closure and the `on_window_event` handler can read/update it.
+pub struct WindowStateManager {
+    state: Mutex<WindowState>,
+    suppress_next_move: Mutex<bool>,
+    save_tx: mpsc::Sender<()>,
+}
+
+impl WindowStateManager {
+    /// Spawns a single long-lived debounce thread shared by every
+    /// `update_geometry` call, rather than one thread per move/resize event.
+    pub fn new(initial: WindowState, app: &AppHandle) -> Self {
+        let (save_tx, save_rx) = mpsc::channel::<()>();
+        let app = app.clone();
+        thread::spawn(move || {
+            while save_rx.recv().is_ok() {
+                // Keep draining pings that arrive during the debounce
+                // window so a drag/resize collapses into one write.
+                while save_rx.recv_timeout(SAVE_DEBOUNCE).is_ok() {}
+                let manager = app.state::<WindowStateManager>();
+                write_state(&app, &manager.snapshot());
+            }
+        });
+        Self {
+            state: Mutex::new(initial),
+            suppress_next_move: Mutex::new(false),
+            save_tx,
+        }
+    }
+
+    pub fn snapshot(&self) -> WindowState {
+        self.state.lock().unwrap().clone()
+    }
+
+    pub fn is_user_positioned(&self) -> bool {
+        self.state.lock().unwrap().user_positioned
+    }
+
+    pub fn mark_user_positioned(&self) {
+        self.state.lock().unwrap().user_positioned = true;
+    }
+
+    /// Drops the "user dragged the panel" latch, e.g. when the saved
+    /// position no longer lands on any connected monitor. Queues a save
+    /// through the debounce thread so a stale off-screen position isn't
+    /// retried next launch.
+    pub fn clear_user_positioned(&self) {
+        self.state.lock().unwrap().user_positioned = false;
+        let _ = self.save_tx.send(());
+    }
+
+    /// Marks the next `Moved` event as caused by our own `set_position` call
+    /// rather than a user drag, so it doesn't flip `user_positioned`.
+    pub fn begin_programmatic_move(&self) {
+        *self.suppress_next_move.lock().unwrap() = true;
+    }
+
+    /// Consumes the suppression flag, returning whether the upcoming move
+    /// event should be ignored.
+    pub fn take_suppressed_move(&self) -> bool {
+        let mut suppressed = self.suppress_next_move.lock().unwrap();
+        if *suppressed {
+            *suppressed = false;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Updates the tracked geometry and pings the debounce thread to
+    /// (eventually) write it to disk.
+    pub fn update_geometry(&self, size: PhysicalSize<u32>, position: PhysicalPosition<i32>) {
+        {
+            let mut state = self.state.lock().unwrap();
+            state.width = size.width;
+            state.height = size.height;
+            state.x = position.x;
+            state.y = position.y;
+        }
+        let _ = self.save_tx.send(());
+    }
+}
+
+fn state_file_path(app: &AppHandle) -> Option<std::path::PathBuf> {
+    app.path().app_config_dir().ok().map(|dir| dir.join(STATE_FILE_NAME))
+}
+
+/// Loads the saved window state, falling back to defaults if the file is
+/// missing or unreadable.
+pub fn load(app: &AppHandle) -> WindowState {
+    state_file_path(app)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn write_state(app: &AppHandle, state: &WindowState) {
+    let Some(path) = state_file_path(app) else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string_pretty(state) {
+        let _ = fs::write(path, json);
+    }
+}